@@ -0,0 +1,154 @@
+//! [`Board`] models a physical Pi header layout as its own [`PinDescription`] set, so a
+//! [`GPIOConfig`] saved on one board can be checked for portability before being applied to
+//! another, rather than silently driving whatever BCM pin the old board happened to have at
+//! that position.
+
+use crate::gpio::hardware::supports_function;
+use crate::gpio::{BCMPinNumber, BoardPinNumber, GPIOConfig, PinDescription, PinFunction, PIN_DESCRIPTIONS};
+
+/// The 26-pin header used by the original Model A/B and their revisions, before the Pi gained
+/// the extra 14 pins in the Model B+. It is pin-compatible with the first 26 pins of the
+/// current 40-pin header.
+pub const PIN_DESCRIPTIONS_26: [PinDescription; 26] = [
+    PIN_DESCRIPTIONS[0], PIN_DESCRIPTIONS[1], PIN_DESCRIPTIONS[2], PIN_DESCRIPTIONS[3],
+    PIN_DESCRIPTIONS[4], PIN_DESCRIPTIONS[5], PIN_DESCRIPTIONS[6], PIN_DESCRIPTIONS[7],
+    PIN_DESCRIPTIONS[8], PIN_DESCRIPTIONS[9], PIN_DESCRIPTIONS[10], PIN_DESCRIPTIONS[11],
+    PIN_DESCRIPTIONS[12], PIN_DESCRIPTIONS[13], PIN_DESCRIPTIONS[14], PIN_DESCRIPTIONS[15],
+    PIN_DESCRIPTIONS[16], PIN_DESCRIPTIONS[17], PIN_DESCRIPTIONS[18], PIN_DESCRIPTIONS[19],
+    PIN_DESCRIPTIONS[20], PIN_DESCRIPTIONS[21], PIN_DESCRIPTIONS[22], PIN_DESCRIPTIONS[23],
+    PIN_DESCRIPTIONS[24], PIN_DESCRIPTIONS[25],
+];
+
+/// Implemented by the Pi header layouts a [`GPIOConfig`] can be validated against.
+pub trait Board {
+    /// A human-readable name for the board model, e.g. for display in the GUI's board picker.
+    fn name(&self) -> &'static str;
+
+    /// The board's full pin header, in `board_pin_number` order.
+    fn pin_descriptions(&self) -> &'static [PinDescription];
+
+    /// Look up the [`PinDescription`] for a BCM GPIO number on this board, if it exists.
+    fn pin_description(&self, bcm_pin_number: BCMPinNumber) -> Option<&'static PinDescription> {
+        self.pin_descriptions()
+            .iter()
+            .find(|pin| pin.bcm_pin_number == Some(bcm_pin_number))
+    }
+
+    /// The `board_pin_number` a BCM GPIO number is wired to on this board, if it exists.
+    fn board_pin_number(&self, bcm_pin_number: BCMPinNumber) -> Option<BoardPinNumber> {
+        self.pin_description(bcm_pin_number)
+            .map(|pin| pin.board_pin_number)
+    }
+}
+
+/// The Pi header layouts [`GPIOConfig::validate_against`] can check a config against.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BoardModel {
+    /// The 40-pin header of all Model B+ and later boards.
+    Pi40Pin,
+    /// The 26-pin header of the original Model A/B.
+    Pi26Pin,
+}
+
+impl Board for BoardModel {
+    fn name(&self) -> &'static str {
+        match self {
+            BoardModel::Pi40Pin => "Raspberry Pi (40-pin)",
+            BoardModel::Pi26Pin => "Raspberry Pi (26-pin)",
+        }
+    }
+
+    fn pin_descriptions(&self) -> &'static [PinDescription] {
+        match self {
+            BoardModel::Pi40Pin => &PIN_DESCRIPTIONS,
+            BoardModel::Pi26Pin => &PIN_DESCRIPTIONS_26,
+        }
+    }
+}
+
+/// One `(BCMPinNumber, PinFunction)` entry of a [`GPIOConfig`] that cannot be carried over to
+/// another [`Board`], as reported by [`GPIOConfig::validate_against`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PortabilityIssue {
+    /// The BCM pin does not exist on the target board's header at all.
+    UnknownPin(BCMPinNumber),
+    /// The BCM pin exists on the target board, but not with the configured [`PinFunction`].
+    UnsupportedFunction {
+        bcm_pin_number: BCMPinNumber,
+        function: PinFunction,
+    },
+}
+
+impl GPIOConfig {
+    /// Check every entry of [`Self::configured_pins`] against `board`, returning one
+    /// [`PortabilityIssue`] per pin/function that `board` cannot support. An empty result means
+    /// this config can be applied to `board` unchanged.
+    pub fn validate_against(&self, board: &impl Board) -> Vec<PortabilityIssue> {
+        self.configured_pins
+            .iter()
+            .filter_map(|(bcm_pin_number, function)| match board.pin_description(*bcm_pin_number) {
+                None => Some(PortabilityIssue::UnknownPin(*bcm_pin_number)),
+                Some(description) if !supports_function(description, function) => {
+                    Some(PortabilityIssue::UnsupportedFunction {
+                        bcm_pin_number: *bcm_pin_number,
+                        function: *function,
+                    })
+                }
+                Some(_) => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::gpio::board::{Board, BoardModel, PortabilityIssue};
+    use crate::gpio::GPIOConfig;
+    use crate::gpio::InputPull::PullUp;
+    use crate::gpio::PinFunction;
+
+    #[test]
+    fn same_board_config_with_pull_and_level_is_portable() {
+        let config = GPIOConfig {
+            configured_pins: vec![
+                (17, PinFunction::Output(Some(true))),
+                (7, PinFunction::Input(Some(PullUp))),
+            ],
+        };
+
+        assert!(config.validate_against(&BoardModel::Pi40Pin).is_empty());
+    }
+
+    #[test]
+    fn unknown_pin_on_smaller_board_is_reported() {
+        let config = GPIOConfig {
+            configured_pins: vec![(21, PinFunction::Input(None))], // GPIO21 is board pin 40, not on the 26-pin header
+        };
+
+        assert_eq!(
+            config.validate_against(&BoardModel::Pi26Pin),
+            vec![PortabilityIssue::UnknownPin(21)]
+        );
+    }
+
+    #[test]
+    fn unsupported_function_on_board_is_reported() {
+        let config = GPIOConfig {
+            configured_pins: vec![(2, PinFunction::SPI0_MOSI)], // GPIO2 only supports I2C1_SDA besides Input/Output
+        };
+
+        assert_eq!(
+            config.validate_against(&BoardModel::Pi40Pin),
+            vec![PortabilityIssue::UnsupportedFunction {
+                bcm_pin_number: 2,
+                function: PinFunction::SPI0_MOSI,
+            }]
+        );
+    }
+
+    #[test]
+    fn board_pin_number_looks_up_bcm_pin() {
+        assert_eq!(BoardModel::Pi40Pin.board_pin_number(21), Some(40));
+        assert_eq!(BoardModel::Pi26Pin.board_pin_number(21), None);
+    }
+}
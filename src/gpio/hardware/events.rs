@@ -0,0 +1,115 @@
+//! Edge-detection events for [`crate::gpio::PinFunction::Input`] pins, so callers can react
+//! to level changes instead of polling [`super::GPIOController::read_level`].
+
+use std::sync::mpsc::Receiver;
+use std::time::Duration;
+
+use crate::gpio::{BCMPinNumber, PinLevel};
+
+use super::HardwareError;
+
+/// Which transitions of a pin's level should be reported.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum EdgeTrigger {
+    Rising,
+    Falling,
+    Both,
+    None,
+}
+
+/// One debounced level change, timestamped by the kernel at the moment the edge was seen.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct GPIOEvent {
+    pub bcm_pin_number: BCMPinNumber,
+    pub level: PinLevel,
+    /// Nanoseconds from `CLOCK_MONOTONIC`, as reported by the kernel for the edge.
+    pub timestamp_ns: u64,
+}
+
+/// A stream of [`GPIOEvent`]s for a watched pin. `recv()`/iteration blocks until the next
+/// debounced edge; wrap in an async executor's blocking-task adapter to use from async code.
+pub type EventStream = Receiver<GPIOEvent>;
+
+/// Implemented by [`super::GPIOController`]s that can deliver edge-triggered events for
+/// [`crate::gpio::PinFunction::Input`] pins, instead of requiring the caller to poll
+/// [`super::GPIOController::read_level`].
+pub trait EventWatcher {
+    /// Start watching `bcm_pin_number` for `trigger` edges and return a stream of the debounced
+    /// events. An edge is reported only once `debounce` has passed with no further edge; each
+    /// edge inside that window replaces the pending one, so a burst of bounces yields a single
+    /// event carrying the final level. The pin must already have been configured as [`crate::gpio::PinFunction::Input`]
+    /// via [`super::GPIOController::apply_config`].
+    fn watch(
+        &mut self,
+        bcm_pin_number: BCMPinNumber,
+        trigger: EdgeTrigger,
+        debounce: Duration,
+    ) -> Result<EventStream, HardwareError>;
+}
+
+/// Holds the most recent observed edge for a pin and decides, via a quiescence window, when
+/// it represents the final settled level rather than one more bounce in a burst. Every newly
+/// observed edge supersedes whatever was pending and restarts the window, so a train of bounces
+/// never gets reported until it has actually stopped.
+#[cfg(all(target_os = "linux", not(feature = "gui")))]
+pub(super) struct Debouncer {
+    window: Duration,
+    pending: Option<GPIOEvent>,
+}
+
+#[cfg(all(target_os = "linux", not(feature = "gui")))]
+impl Debouncer {
+    pub(super) fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: None,
+        }
+    }
+
+    /// Record a newly observed edge, discarding whatever edge was still pending.
+    pub(super) fn observe(&mut self, event: GPIOEvent) {
+        self.pending = Some(event);
+    }
+
+    /// Take the pending edge, if any - call this once the caller has waited a full `window`
+    /// since the last [`Self::observe`] with no further edge arriving.
+    pub(super) fn settle(&mut self) -> Option<GPIOEvent> {
+        self.pending.take()
+    }
+
+    /// How long the caller should wait for another edge before treating the pending one (if
+    /// any) as settled: the full window while an edge is pending, or block indefinitely
+    /// (`-1`) while idle. Saturates to `i32::MAX` ms rather than overflow for very long windows.
+    pub(super) fn poll_timeout_ms(&self) -> i32 {
+        if self.pending.is_some() {
+            self.window.as_millis().try_into().unwrap_or(i32::MAX)
+        } else {
+            -1
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(target_os = "linux", not(feature = "gui")))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bounce_train_settles_on_final_level() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(10));
+        assert_eq!(debouncer.poll_timeout_ms(), -1);
+
+        debouncer.observe(GPIOEvent { bcm_pin_number: 4, level: false, timestamp_ns: 0 });
+        assert_eq!(debouncer.poll_timeout_ms(), 10);
+
+        // A second, later edge supersedes the first instead of being dropped.
+        debouncer.observe(GPIOEvent { bcm_pin_number: 4, level: true, timestamp_ns: 1_000_000 });
+        debouncer.observe(GPIOEvent { bcm_pin_number: 4, level: false, timestamp_ns: 2_000_000 });
+
+        // Only once no further edge arrives does the final, settled level come out.
+        let settled = debouncer.settle().expect("an edge was pending");
+        assert!(!settled.level);
+        assert_eq!(settled.timestamp_ns, 2_000_000);
+        assert!(debouncer.settle().is_none());
+    }
+}
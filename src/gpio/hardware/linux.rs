@@ -0,0 +1,517 @@
+//! Linux backend for [`super::GPIOController`].
+//!
+//! Pins are configured through the GPIO character device (`/dev/gpiochipN`), requesting one
+//! line per configured pin with the GPIO v2 uAPI. Kernels too old to expose the chardev (pre
+//! 4.8, or with `CONFIG_GPIO_CDEV` disabled) fall back to the legacy sysfs interface under
+//! `/sys/class/gpio`.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io;
+use std::mem;
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+use crate::gpio::{BCMPinNumber, GPIOConfig, InputPull, PinFunction, PinLevel};
+
+use super::events::Debouncer;
+use super::registers::Registers;
+use super::{validate_function, EdgeTrigger, EventWatcher, GPIOController, GPIOEvent, HardwareError};
+
+const GPIOCHIP_PATH: &str = "/dev/gpiochip0";
+const SYSFS_GPIO_ROOT: &str = "/sys/class/gpio";
+
+// GPIO v2 uAPI, see <linux/gpio.h>
+const GPIO_V2_LINE_FLAG_INPUT: u64 = 1 << 2;
+const GPIO_V2_LINE_FLAG_OUTPUT: u64 = 1 << 3;
+const GPIO_V2_LINE_FLAG_EDGE_RISING: u64 = 1 << 4;
+const GPIO_V2_LINE_FLAG_EDGE_FALLING: u64 = 1 << 5;
+const GPIO_V2_LINE_FLAG_BIAS_PULL_UP: u64 = 1 << 8;
+const GPIO_V2_LINE_FLAG_BIAS_PULL_DOWN: u64 = 1 << 9;
+
+const GPIO_V2_LINE_ATTR_ID_OUTPUT_VALUES: u32 = 2;
+const GPIO_MAX_NAME_SIZE: usize = 32;
+const GPIO_V2_LINE_NUM_ATTRS_MAX: usize = 10;
+const GPIO_V2_LINE_EVENT_BUFFER_SIZE: u32 = 16;
+
+/// `gpio_v2_line_event.id`: the edge that occurred.
+const GPIO_V2_LINE_EVENT_RISING_EDGE: u32 = 1;
+const GPIO_V2_LINE_EVENT_FALLING_EDGE: u32 = 2;
+
+const GPIO_IOC_MAGIC: u8 = 0xB4;
+const GPIO_V2_GET_LINE_IOCTL_NR: u8 = 0x07;
+const GPIO_V2_LINE_GET_VALUES_IOCTL_NR: u8 = 0x0E;
+const GPIO_V2_LINE_SET_VALUES_IOCTL_NR: u8 = 0x0F;
+
+const POLLIN: i16 = 0x0001;
+
+/// `_IOWR(GPIO_IOC_MAGIC, nr, T)` as used throughout `<linux/gpio.h>`.
+const fn iowr<T>(nr: u8) -> u64 {
+    const IOC_READ_WRITE: u64 = 3 << 30;
+    let size = mem::size_of::<T>() as u64;
+    IOC_READ_WRITE | (size << 16) | ((GPIO_IOC_MAGIC as u64) << 8) | nr as u64
+}
+
+#[repr(C)]
+struct GpioV2LineAttribute {
+    id: u32,
+    padding: u32,
+    value: u64,
+}
+
+#[repr(C)]
+struct GpioV2LineConfigAttribute {
+    attr: GpioV2LineAttribute,
+    mask: u64,
+}
+
+#[repr(C)]
+struct GpioV2LineConfig {
+    flags: u64,
+    num_attrs: u32,
+    padding: [u32; 5],
+    attrs: [GpioV2LineConfigAttribute; GPIO_V2_LINE_NUM_ATTRS_MAX],
+}
+
+#[repr(C)]
+struct GpioV2LineRequest {
+    offsets: [u32; 64],
+    consumer: [u8; GPIO_MAX_NAME_SIZE],
+    config: GpioV2LineConfig,
+    num_lines: u32,
+    event_buffer_size: u32,
+    padding: [u32; 5],
+    fd: i32,
+}
+
+#[repr(C)]
+struct GpioV2LineValues {
+    bits: u64,
+    mask: u64,
+}
+
+#[repr(C)]
+struct GpioV2LineEvent {
+    timestamp_ns: u64,
+    id: u32,
+    offset: u32,
+    seqno: u32,
+    line_seqno: u32,
+    padding: [u32; 6],
+}
+
+#[repr(C)]
+struct PollFd {
+    fd: i32,
+    events: i16,
+    revents: i16,
+}
+
+extern "C" {
+    fn ioctl(fd: i32, request: u64, ...) -> i32;
+    fn poll(fds: *mut PollFd, nfds: u64, timeout: i32) -> i32;
+    fn read(fd: i32, buf: *mut std::ffi::c_void, count: usize) -> isize;
+}
+
+fn consumer_name() -> [u8; GPIO_MAX_NAME_SIZE] {
+    let mut name = [0u8; GPIO_MAX_NAME_SIZE];
+    for (dst, src) in name.iter_mut().zip(b"pigg") {
+        *dst = *src;
+    }
+    name
+}
+
+/// One GPIO line requested from the chardev, kept open for as long as the controller holds
+/// this pin configured.
+struct Line {
+    fd: File,
+    function: PinFunction,
+}
+
+/// [`GPIOController`] backed by the Linux GPIO character device, with a sysfs fallback for
+/// kernels without `/dev/gpiochipN`.
+pub struct LinuxGPIOController {
+    chip: Option<File>,
+    lines: HashMap<BCMPinNumber, Line>,
+    sysfs_exported: Vec<BCMPinNumber>,
+    registers: Option<Registers>,
+}
+
+impl LinuxGPIOController {
+    /// Open the GPIO chardev, falling back to remembering that sysfs should be used instead
+    /// if the chardev is not present on this kernel.
+    pub fn new() -> Result<Self, HardwareError> {
+        let chip = match File::open(GPIOCHIP_PATH) {
+            Ok(file) => Some(file),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+            Err(error) => return Err(error.into()),
+        };
+
+        Ok(Self {
+            chip,
+            lines: HashMap::new(),
+            sysfs_exported: Vec::new(),
+            registers: None,
+        })
+    }
+
+    /// Map the clock manager / PWM controller registers on first use, reusing the mapping
+    /// after that.
+    fn registers(&mut self) -> Result<&Registers, HardwareError> {
+        if self.registers.is_none() {
+            self.registers = Some(Registers::map()?);
+        }
+        Ok(self.registers.as_ref().expect("just set"))
+    }
+
+    fn request_line(
+        &self,
+        bcm_pin_number: BCMPinNumber,
+        function: &PinFunction,
+    ) -> Result<File, HardwareError> {
+        let chip = self.chip.as_ref().expect("chardev checked by caller");
+
+        let mut flags = 0u64;
+        let mut config = GpioV2LineConfig {
+            flags: 0,
+            num_attrs: 0,
+            padding: [0; 5],
+            attrs: unsafe { mem::zeroed() },
+        };
+
+        match function {
+            PinFunction::Input(pull) => {
+                flags |= GPIO_V2_LINE_FLAG_INPUT;
+                flags |= match pull {
+                    Some(InputPull::PullUp) => GPIO_V2_LINE_FLAG_BIAS_PULL_UP,
+                    Some(InputPull::PullDown) => GPIO_V2_LINE_FLAG_BIAS_PULL_DOWN,
+                    Some(InputPull::None) | None => 0,
+                };
+            }
+            PinFunction::Output(initial_level) => {
+                flags |= GPIO_V2_LINE_FLAG_OUTPUT;
+                if let Some(level) = initial_level {
+                    config.num_attrs = 1;
+                    config.attrs[0] = GpioV2LineConfigAttribute {
+                        attr: GpioV2LineAttribute {
+                            id: GPIO_V2_LINE_ATTR_ID_OUTPUT_VALUES,
+                            padding: 0,
+                            value: *level as u64,
+                        },
+                        mask: 1,
+                    };
+                }
+            }
+            other => {
+                return Err(HardwareError::UnsupportedFunction {
+                    bcm_pin_number,
+                    function: *other,
+                })
+            }
+        }
+        config.flags = flags;
+
+        let mut request = GpioV2LineRequest {
+            offsets: [0; 64],
+            consumer: consumer_name(),
+            config,
+            num_lines: 1,
+            event_buffer_size: 0,
+            padding: [0; 5],
+            fd: -1,
+        };
+        request.offsets[0] = bcm_pin_number as u32;
+
+        let result = unsafe {
+            ioctl(
+                chip.as_raw_fd(),
+                iowr::<GpioV2LineRequest>(GPIO_V2_GET_LINE_IOCTL_NR),
+                &mut request as *mut GpioV2LineRequest,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        // SAFETY: the kernel filled in `request.fd` with a freshly opened, owned fd.
+        Ok(unsafe { File::from_raw_fd(request.fd as RawFd) })
+    }
+
+    /// Request a dedicated line, separate from the one opened for direction/level by
+    /// [`Self::apply_config`], configured to report `trigger` edges.
+    fn request_event_line(
+        &self,
+        bcm_pin_number: BCMPinNumber,
+        trigger: EdgeTrigger,
+    ) -> Result<File, HardwareError> {
+        let chip = self
+            .chip
+            .as_ref()
+            .ok_or_else(|| HardwareError::Io("GPIO chardev not available for event watching".into()))?;
+
+        let mut flags = GPIO_V2_LINE_FLAG_INPUT;
+        flags |= match trigger {
+            EdgeTrigger::Rising => GPIO_V2_LINE_FLAG_EDGE_RISING,
+            EdgeTrigger::Falling => GPIO_V2_LINE_FLAG_EDGE_FALLING,
+            EdgeTrigger::Both => GPIO_V2_LINE_FLAG_EDGE_RISING | GPIO_V2_LINE_FLAG_EDGE_FALLING,
+            EdgeTrigger::None => 0,
+        };
+
+        let mut request = GpioV2LineRequest {
+            offsets: [0; 64],
+            consumer: consumer_name(),
+            config: GpioV2LineConfig {
+                flags,
+                num_attrs: 0,
+                padding: [0; 5],
+                attrs: unsafe { mem::zeroed() },
+            },
+            num_lines: 1,
+            event_buffer_size: GPIO_V2_LINE_EVENT_BUFFER_SIZE,
+            padding: [0; 5],
+            fd: -1,
+        };
+        request.offsets[0] = bcm_pin_number as u32;
+
+        let result = unsafe {
+            ioctl(
+                chip.as_raw_fd(),
+                iowr::<GpioV2LineRequest>(GPIO_V2_GET_LINE_IOCTL_NR),
+                &mut request as *mut GpioV2LineRequest,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        // SAFETY: the kernel filled in `request.fd` with a freshly opened, owned fd.
+        Ok(unsafe { File::from_raw_fd(request.fd as RawFd) })
+    }
+
+    fn line_get_value(&self, line: &Line) -> Result<PinLevel, HardwareError> {
+        let mut values = GpioV2LineValues { bits: 0, mask: 1 };
+        let result = unsafe {
+            ioctl(
+                line.fd.as_raw_fd(),
+                iowr::<GpioV2LineValues>(GPIO_V2_LINE_GET_VALUES_IOCTL_NR),
+                &mut values as *mut GpioV2LineValues,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+        Ok(values.bits & 1 == 1)
+    }
+
+    fn line_set_value(&self, line: &Line, level: PinLevel) -> Result<(), HardwareError> {
+        let mut values = GpioV2LineValues {
+            bits: level as u64,
+            mask: 1,
+        };
+        let result = unsafe {
+            ioctl(
+                line.fd.as_raw_fd(),
+                iowr::<GpioV2LineValues>(GPIO_V2_LINE_SET_VALUES_IOCTL_NR),
+                &mut values as *mut GpioV2LineValues,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+        Ok(())
+    }
+
+    // --- sysfs fallback, used when `/dev/gpiochipN` does not exist ---
+
+    fn sysfs_export(&mut self, bcm_pin_number: BCMPinNumber) -> Result<(), HardwareError> {
+        let pin_path = format!("{SYSFS_GPIO_ROOT}/gpio{bcm_pin_number}");
+        if fs::metadata(&pin_path).is_err() {
+            fs::write(format!("{SYSFS_GPIO_ROOT}/export"), bcm_pin_number.to_string())?;
+            self.sysfs_exported.push(bcm_pin_number);
+        }
+        Ok(())
+    }
+
+    fn sysfs_apply(
+        &mut self,
+        bcm_pin_number: BCMPinNumber,
+        function: &PinFunction,
+    ) -> Result<(), HardwareError> {
+        self.sysfs_export(bcm_pin_number)?;
+        let pin_path = format!("{SYSFS_GPIO_ROOT}/gpio{bcm_pin_number}");
+
+        match function {
+            PinFunction::Input(_) => {
+                // sysfs has no bias control, so any InputPull is not applied on this path.
+                fs::write(format!("{pin_path}/direction"), "in")?;
+            }
+            PinFunction::Output(initial_level) => {
+                fs::write(format!("{pin_path}/direction"), "out")?;
+                if let Some(level) = initial_level {
+                    let value = if *level { "1" } else { "0" };
+                    fs::write(format!("{pin_path}/value"), value)?;
+                }
+            }
+            other => {
+                return Err(HardwareError::UnsupportedFunction {
+                    bcm_pin_number,
+                    function: *other,
+                })
+            }
+        }
+        Ok(())
+    }
+
+    fn sysfs_read(&self, bcm_pin_number: BCMPinNumber) -> Result<PinLevel, HardwareError> {
+        let value = fs::read_to_string(format!("{SYSFS_GPIO_ROOT}/gpio{bcm_pin_number}/value"))?;
+        Ok(value.trim() == "1")
+    }
+
+    fn sysfs_write(&self, bcm_pin_number: BCMPinNumber, level: PinLevel) -> Result<(), HardwareError> {
+        let value = if level { "1" } else { "0" };
+        fs::write(format!("{SYSFS_GPIO_ROOT}/gpio{bcm_pin_number}/value"), value)?;
+        Ok(())
+    }
+}
+
+impl GPIOController for LinuxGPIOController {
+    fn apply_config(&mut self, config: &GPIOConfig) -> Result<(), HardwareError> {
+        for (bcm_pin_number, function) in &config.configured_pins {
+            validate_function(*bcm_pin_number, function)?;
+
+            match function {
+                PinFunction::GPCLK0(clock) => self.registers()?.set_clock(0, *clock),
+                PinFunction::GPCLK1(clock) => self.registers()?.set_clock(1, *clock),
+                PinFunction::GPCLK2(clock) => self.registers()?.set_clock(2, *clock),
+                PinFunction::PWM0(pwm) => self.registers()?.set_pwm(0, *pwm),
+                PinFunction::PWM1(pwm) => self.registers()?.set_pwm(1, *pwm),
+                _ if self.chip.is_some() => {
+                    let fd = self.request_line(*bcm_pin_number, function)?;
+                    self.lines.insert(
+                        *bcm_pin_number,
+                        Line {
+                            fd,
+                            function: *function,
+                        },
+                    );
+                }
+                _ => self.sysfs_apply(*bcm_pin_number, function)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn read_level(&self, bcm_pin_number: BCMPinNumber) -> Result<PinLevel, HardwareError> {
+        if self.chip.is_some() {
+            let line = self
+                .lines
+                .get(&bcm_pin_number)
+                .ok_or(HardwareError::PinNotConfigured(bcm_pin_number))?;
+            self.line_get_value(line)
+        } else {
+            self.sysfs_read(bcm_pin_number)
+        }
+    }
+
+    fn write_level(&mut self, bcm_pin_number: BCMPinNumber, level: PinLevel) -> Result<(), HardwareError> {
+        if self.chip.is_some() {
+            let line = self
+                .lines
+                .get(&bcm_pin_number)
+                .ok_or(HardwareError::PinNotConfigured(bcm_pin_number))?;
+            if !matches!(line.function, PinFunction::Output(_)) {
+                return Err(HardwareError::UnsupportedFunction {
+                    bcm_pin_number,
+                    function: line.function,
+                });
+            }
+            self.line_set_value(line, level)
+        } else {
+            self.sysfs_write(bcm_pin_number, level)
+        }
+    }
+}
+
+impl EventWatcher for LinuxGPIOController {
+    fn watch(
+        &mut self,
+        bcm_pin_number: BCMPinNumber,
+        trigger: EdgeTrigger,
+        debounce: Duration,
+    ) -> Result<Receiver<GPIOEvent>, HardwareError> {
+        validate_function(bcm_pin_number, &PinFunction::Input(None))?;
+        let event_line = self.request_event_line(bcm_pin_number, trigger)?;
+
+        let (sender, receiver) = mpsc::channel();
+        let fd = event_line.as_raw_fd();
+
+        thread::spawn(move || {
+            // Owning `event_line` here keeps the fd (and the kernel's line request) alive for
+            // as long as this thread runs; it closes automatically when the thread exits.
+            let _event_line = event_line;
+            let mut debouncer = Debouncer::new(debounce);
+            let mut fds = [PollFd {
+                fd,
+                events: POLLIN,
+                revents: 0,
+            }];
+            let mut buffer = [0u8; mem::size_of::<GpioV2LineEvent>()];
+
+            loop {
+                let ready = unsafe {
+                    poll(fds.as_mut_ptr(), fds.len() as u64, debouncer.poll_timeout_ms())
+                };
+
+                if ready == 0 {
+                    // No further edge arrived within the window: the pending one has settled.
+                    if let Some(event) = debouncer.settle() {
+                        if sender.send(event).is_err() {
+                            break; // receiver dropped, stop watching
+                        }
+                    }
+                    continue;
+                }
+                if ready < 0 {
+                    continue;
+                }
+
+                let bytes_read = unsafe {
+                    read(
+                        fd,
+                        buffer.as_mut_ptr() as *mut std::ffi::c_void,
+                        buffer.len(),
+                    )
+                };
+                if bytes_read != buffer.len() as isize {
+                    break;
+                }
+
+                // SAFETY: `buffer` holds exactly `size_of::<GpioV2LineEvent>()` bytes just
+                // read from the kernel.
+                let event: GpioV2LineEvent = unsafe { std::ptr::read(buffer.as_ptr() as *const _) };
+                let level = event.id == GPIO_V2_LINE_EVENT_RISING_EDGE;
+                debug_assert!(level || event.id == GPIO_V2_LINE_EVENT_FALLING_EDGE);
+
+                debouncer.observe(GPIOEvent {
+                    bcm_pin_number,
+                    level,
+                    timestamp_ns: event.timestamp_ns,
+                });
+            }
+        });
+
+        Ok(receiver)
+    }
+}
+
+impl Drop for LinuxGPIOController {
+    fn drop(&mut self) {
+        // Line fds close themselves via `File`'s Drop. Only sysfs-exported pins need explicit
+        // unexport, since chardev lines are released automatically when their fd closes.
+        for bcm_pin_number in self.sysfs_exported.drain(..) {
+            let _ = fs::write(format!("{SYSFS_GPIO_ROOT}/unexport"), bcm_pin_number.to_string());
+        }
+    }
+}
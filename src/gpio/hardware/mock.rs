@@ -0,0 +1,137 @@
+//! No-op backend used by the `gui` feature so the UI can be developed and demoed off-target,
+//! without a real Raspberry Pi GPIO header to drive.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+use crate::gpio::{BCMPinNumber, GPIOConfig, PinFunction, PinLevel};
+
+use super::{validate_function, EdgeTrigger, EventWatcher, GPIOController, GPIOEvent, HardwareError};
+
+/// A [`GPIOController`] that validates configuration the same way real hardware would, but
+/// only ever reads and writes an in-memory level per pin.
+#[derive(Default)]
+pub struct MockGPIOController {
+    levels: HashMap<BCMPinNumber, PinLevel>,
+    functions: HashMap<BCMPinNumber, PinFunction>,
+}
+
+impl MockGPIOController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl GPIOController for MockGPIOController {
+    fn apply_config(&mut self, config: &GPIOConfig) -> Result<(), HardwareError> {
+        for (bcm_pin_number, function) in &config.configured_pins {
+            validate_function(*bcm_pin_number, function)?;
+
+            let initial_level = match function {
+                PinFunction::Input(_) => false,
+                PinFunction::Output(level) => level.unwrap_or(false),
+                _ => false,
+            };
+
+            self.functions.insert(*bcm_pin_number, *function);
+            self.levels.insert(*bcm_pin_number, initial_level);
+        }
+        Ok(())
+    }
+
+    fn read_level(&self, bcm_pin_number: BCMPinNumber) -> Result<PinLevel, HardwareError> {
+        self.levels
+            .get(&bcm_pin_number)
+            .copied()
+            .ok_or(HardwareError::PinNotConfigured(bcm_pin_number))
+    }
+
+    fn write_level(&mut self, bcm_pin_number: BCMPinNumber, level: PinLevel) -> Result<(), HardwareError> {
+        match self.functions.get(&bcm_pin_number) {
+            Some(PinFunction::Output(_)) => {
+                self.levels.insert(bcm_pin_number, level);
+                Ok(())
+            }
+            Some(other) => Err(HardwareError::UnsupportedFunction {
+                bcm_pin_number,
+                function: *other,
+            }),
+            None => Err(HardwareError::PinNotConfigured(bcm_pin_number)),
+        }
+    }
+}
+
+impl EventWatcher for MockGPIOController {
+    /// There is no real hardware to raise edges, so this simply validates the pin and returns
+    /// a stream that never yields an event.
+    fn watch(
+        &mut self,
+        bcm_pin_number: BCMPinNumber,
+        _trigger: EdgeTrigger,
+        _debounce: Duration,
+    ) -> Result<Receiver<GPIOEvent>, HardwareError> {
+        validate_function(bcm_pin_number, &PinFunction::Input(None))?;
+        let (_sender, receiver) = mpsc::channel();
+        Ok(receiver)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn apply_config_then_read_level() {
+        let mut controller = MockGPIOController::new();
+        let config = GPIOConfig {
+            configured_pins: vec![(17, PinFunction::Output(Some(true)))],
+        };
+
+        controller.apply_config(&config).unwrap();
+
+        assert!(controller.read_level(17).unwrap());
+    }
+
+    #[test]
+    fn write_level_on_unconfigured_pin_errors() {
+        let mut controller = MockGPIOController::new();
+        let error = controller.write_level(17, true).unwrap_err();
+        assert!(matches!(error, HardwareError::PinNotConfigured(17)));
+    }
+
+    #[test]
+    fn write_level_on_input_pin_errors() {
+        let mut controller = MockGPIOController::new();
+        let config = GPIOConfig {
+            configured_pins: vec![(17, PinFunction::Input(None))],
+        };
+        controller.apply_config(&config).unwrap();
+
+        let error = controller.write_level(17, true).unwrap_err();
+        assert!(matches!(
+            error,
+            HardwareError::UnsupportedFunction {
+                bcm_pin_number: 17,
+                function: PinFunction::Input(None)
+            }
+        ));
+    }
+
+    #[test]
+    fn apply_config_rejects_unsupported_function() {
+        let mut controller = MockGPIOController::new();
+        let config = GPIOConfig {
+            configured_pins: vec![(17, PinFunction::SPI0_MOSI)], // GPIO17 has no SPI0 option
+        };
+
+        let error = controller.apply_config(&config).unwrap_err();
+        assert!(matches!(
+            error,
+            HardwareError::UnsupportedFunction {
+                bcm_pin_number: 17,
+                function: PinFunction::SPI0_MOSI
+            }
+        ));
+    }
+}
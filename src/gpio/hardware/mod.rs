@@ -0,0 +1,136 @@
+use std::fmt;
+use std::mem;
+
+mod events;
+#[cfg(all(target_os = "linux", not(feature = "gui")))]
+mod linux;
+#[cfg(feature = "gui")]
+mod mock;
+#[cfg(all(target_os = "linux", not(feature = "gui")))]
+mod registers;
+
+pub use events::{EdgeTrigger, EventWatcher, GPIOEvent};
+#[cfg(all(target_os = "linux", not(feature = "gui")))]
+pub use linux::LinuxGPIOController;
+#[cfg(feature = "gui")]
+pub use mock::MockGPIOController;
+
+use crate::gpio::{pin_description, BCMPinNumber, GPIOConfig, PinDescription, PinFunction, PinLevel};
+
+/// Errors that can occur while applying a [`GPIOConfig`] to real hardware, or while
+/// reading/writing the level of a pin once configured.
+#[derive(Debug)]
+pub enum HardwareError {
+    /// The BCM pin number does not exist on this board's header.
+    UnknownPin(BCMPinNumber),
+    /// The requested [`PinFunction`] is not one of the pin's [`crate::gpio::PinDescription::options`],
+    /// or is one this backend cannot drive, or the pin's configured function does not allow the
+    /// operation (e.g. writing a level to a [`PinFunction::Input`] pin).
+    UnsupportedFunction {
+        bcm_pin_number: BCMPinNumber,
+        function: PinFunction,
+    },
+    /// A level was requested for a pin that has not been configured as an [`PinFunction::Input`]
+    /// or [`PinFunction::Output`].
+    PinNotConfigured(BCMPinNumber),
+    /// An underlying I/O error talking to the kernel GPIO interface.
+    Io(String),
+}
+
+impl fmt::Display for HardwareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HardwareError::UnknownPin(bcm) => write!(f, "BCM pin {bcm} does not exist on this board"),
+            HardwareError::UnsupportedFunction { bcm_pin_number, function } => write!(
+                f,
+                "BCM pin {bcm_pin_number} does not support function {function}"
+            ),
+            HardwareError::PinNotConfigured(bcm) => {
+                write!(f, "BCM pin {bcm} has not been configured as an input or output")
+            }
+            HardwareError::Io(message) => write!(f, "I/O error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for HardwareError {}
+
+impl From<std::io::Error> for HardwareError {
+    fn from(error: std::io::Error) -> Self {
+        HardwareError::Io(error.to_string())
+    }
+}
+
+/// Implemented by anything that can apply a [`GPIOConfig`] to real (or simulated) pins and
+/// then read back or drive the level of the pins it configured as GPIO.
+pub trait GPIOController {
+    /// Apply every `(BCMPinNumber, PinFunction)` entry in `config` to hardware: set the pin
+    /// direction, apply any [`crate::gpio::InputPull`] and drive the initial
+    /// [`PinLevel`] for outputs.
+    fn apply_config(&mut self, config: &GPIOConfig) -> Result<(), HardwareError>;
+
+    /// Read the current level of a pin previously configured as [`PinFunction::Input`] or
+    /// [`PinFunction::Output`].
+    fn read_level(&self, bcm_pin_number: BCMPinNumber) -> Result<PinLevel, HardwareError>;
+
+    /// Drive a pin previously configured as [`PinFunction::Output`] to `level`.
+    fn write_level(&mut self, bcm_pin_number: BCMPinNumber, level: PinLevel) -> Result<(), HardwareError>;
+}
+
+/// Check that `function` is one of `description`'s [`PinDescription::options`], comparing by
+/// variant only (so e.g. `Input(Some(PullUp))` matches an `options` entry of `Input(None)`).
+pub(crate) fn supports_function(description: &PinDescription, function: &PinFunction) -> bool {
+    description
+        .options
+        .iter()
+        .any(|option| mem::discriminant(option) == mem::discriminant(function))
+}
+
+/// Check that `function` is one of the [`crate::gpio::PinDescription::options`] for
+/// `bcm_pin_number`, comparing by variant only (so e.g. `Input(Some(PullUp))` matches an
+/// `options` entry of `Input(None)`).
+pub(crate) fn validate_function(
+    bcm_pin_number: BCMPinNumber,
+    function: &PinFunction,
+) -> Result<(), HardwareError> {
+    let description = pin_description(bcm_pin_number).ok_or(HardwareError::UnknownPin(bcm_pin_number))?;
+
+    if supports_function(description, function) {
+        Ok(())
+    } else {
+        Err(HardwareError::UnsupportedFunction {
+            bcm_pin_number,
+            function: *function,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gpio::InputPull::PullUp;
+
+    #[test]
+    fn validate_function_matches_by_variant_not_value() {
+        // GPIO17's options list Input(None), but a real Input with a pull should still match.
+        assert!(validate_function(17, &PinFunction::Input(Some(PullUp))).is_ok());
+    }
+
+    #[test]
+    fn validate_function_rejects_unsupported_function() {
+        let error = validate_function(17, &PinFunction::SPI0_MOSI).unwrap_err();
+        assert!(matches!(
+            error,
+            HardwareError::UnsupportedFunction {
+                bcm_pin_number: 17,
+                function: PinFunction::SPI0_MOSI
+            }
+        ));
+    }
+
+    #[test]
+    fn validate_function_rejects_unknown_pin() {
+        let error = validate_function(99, &PinFunction::Input(None)).unwrap_err();
+        assert!(matches!(error, HardwareError::UnknownPin(99)));
+    }
+}
@@ -0,0 +1,126 @@
+//! Direct access to the BCM SoC's clock manager and PWM controller registers.
+//!
+//! The GPIO chardev only knows about digital input/output lines - it has no concept of
+//! `GPCLKn`/`PWMn`, so programming their frequency/duty-cycle means reaching the peripherals
+//! that actually generate those waveforms, by memory-mapping their registers directly.
+
+use std::fs::OpenOptions;
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::ptr;
+
+use crate::gpio::{ClockConfig, PwmConfig};
+
+use super::HardwareError;
+
+/// Peripheral base address on the BCM2711 (Raspberry Pi 4). Earlier SoCs (BCM2835/6/7) use
+/// `0x2000_0000`/`0x3F00_0000` instead; this would need to be read from
+/// `/proc/device-tree/soc/ranges` to support those boards too.
+const PERIPHERAL_BASE: i64 = 0xFE00_0000;
+const MAPPING_SIZE: usize = 0x0021_0000;
+
+const CM_PASSWORD: u32 = 0x5A00_0000;
+const CM_GP_CTL: [usize; 3] = [0x101070, 0x101078, 0x101080];
+const CM_GP_DIV: [usize; 3] = [0x101074, 0x10107C, 0x101084];
+const CM_ENABLE: u32 = 1 << 4;
+const CM_SRC_OSCILLATOR: u32 = 1;
+const OSCILLATOR_HZ: u32 = 54_000_000;
+
+const PWM_BASE_OFFSET: usize = 0x20C000;
+const PWM_CTL: usize = 0x00;
+const PWM_RNG1: usize = 0x10;
+const PWM_DAT1: usize = 0x14;
+const PWM_RNG2: usize = 0x20;
+const PWM_DAT2: usize = 0x24;
+const PWM_PWEN1: u32 = 1 << 0;
+const PWM_PWEN2: u32 = 1 << 8;
+
+const PROT_READ: i32 = 0x1;
+const PROT_WRITE: i32 = 0x2;
+const MAP_SHARED: i32 = 0x01;
+
+extern "C" {
+    fn mmap(
+        addr: *mut std::ffi::c_void,
+        len: usize,
+        prot: i32,
+        flags: i32,
+        fd: i32,
+        offset: i64,
+    ) -> *mut std::ffi::c_void;
+}
+
+/// A memory-mapped window onto the BCM peripheral register block, used to reach the clock
+/// manager and PWM controller that the GPIO chardev has no equivalent for.
+pub(super) struct Registers {
+    base: *mut u32,
+}
+
+// SAFETY: `base` points at device registers accessed only through `write_volatile`.
+unsafe impl Send for Registers {}
+
+impl Registers {
+    pub(super) fn map() -> Result<Self, HardwareError> {
+        let mem = OpenOptions::new().read(true).write(true).open("/dev/mem")?;
+        let ptr = unsafe {
+            mmap(
+                ptr::null_mut(),
+                MAPPING_SIZE,
+                PROT_READ | PROT_WRITE,
+                MAP_SHARED,
+                mem.as_raw_fd(),
+                PERIPHERAL_BASE,
+            )
+        };
+        if ptr as isize == -1 {
+            return Err(io::Error::last_os_error().into());
+        }
+        Ok(Self { base: ptr as *mut u32 })
+    }
+
+    unsafe fn write(&self, byte_offset: usize, value: u32) {
+        self.base.add(byte_offset / 4).write_volatile(value);
+    }
+
+    unsafe fn read(&self, byte_offset: usize) -> u32 {
+        self.base.add(byte_offset / 4).read_volatile()
+    }
+
+    /// Program `GPCLKn` (`index` 0, 1 or 2) to `config.frequency_hz`, sourced from the
+    /// BCM2711's 54MHz crystal oscillator, by setting the clock manager's integer divisor - see the
+    /// "General Purpose GPIO Clocks" section of the BCM2835 ARM Peripherals manual.
+    pub(super) fn set_clock(&self, index: usize, config: ClockConfig) {
+        let divisor = OSCILLATOR_HZ
+            .checked_div(config.frequency_hz)
+            .map_or(0, |divisor| divisor.max(1));
+        unsafe {
+            // The clock must be disabled before its divisor is changed.
+            self.write(CM_GP_CTL[index], CM_PASSWORD | CM_SRC_OSCILLATOR);
+            self.write(CM_GP_DIV[index], CM_PASSWORD | (divisor << 12));
+            if config.frequency_hz > 0 {
+                self.write(CM_GP_CTL[index], CM_PASSWORD | CM_SRC_OSCILLATOR | CM_ENABLE);
+            }
+        }
+    }
+
+    /// Program PWM `channel` (0 for `PWM0`, 1 for `PWM1`) to `config`'s frequency and duty
+    /// cycle by setting the controller's range and data registers. `PWM0` and `PWM1` share a
+    /// single `CTL` register, so its enable bit is read-modify-written rather than overwritten,
+    /// to avoid clobbering the other channel's enable state.
+    pub(super) fn set_pwm(&self, channel: usize, config: PwmConfig) {
+        let range = OSCILLATOR_HZ.checked_div(config.frequency_hz).unwrap_or(0);
+        let data = (range as f32 * config.duty_cycle.clamp(0.0, 1.0)) as u32;
+        let (rng_offset, dat_offset, enable_bit) = if channel == 0 {
+            (PWM_RNG1, PWM_DAT1, PWM_PWEN1)
+        } else {
+            (PWM_RNG2, PWM_DAT2, PWM_PWEN2)
+        };
+        unsafe {
+            self.write(PWM_BASE_OFFSET + rng_offset, range);
+            self.write(PWM_BASE_OFFSET + dat_offset, data);
+            let ctl = self.read(PWM_BASE_OFFSET + PWM_CTL) & !enable_bit;
+            let ctl = if config.frequency_hz > 0 { ctl | enable_bit } else { ctl };
+            self.write(PWM_BASE_OFFSET + PWM_CTL, ctl);
+        }
+    }
+}
@@ -1,10 +1,18 @@
 use std::fmt;
+#[cfg(feature = "gui")]
 use std::fs::File;
+#[cfg(feature = "gui")]
 use std::io;
+#[cfg(feature = "gui")]
 use std::io::{BufReader, Write};
 
 use serde::{Deserialize, Serialize};
 
+pub mod board;
+pub mod hardware;
+pub mod peripheral;
+pub mod remap;
+
 pub type BCMPinNumber = u8;
 pub type BoardPinNumber = u8;
 
@@ -26,6 +34,25 @@ impl fmt::Display for InputPull {
     }
 }
 
+/// Configuration for a `GPCLKn` general purpose clock output.
+#[derive(Debug, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ClockConfig {
+    /// The clock frequency to generate, in Hz. `0` means the clock is left disabled.
+    #[serde(default)]
+    pub frequency_hz: u32,
+}
+
+/// Configuration for a `PWMn` pulse-width modulation output.
+#[derive(Debug, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PwmConfig {
+    /// The PWM frequency to generate, in Hz. `0` means the output is left disabled.
+    #[serde(default)]
+    pub frequency_hz: u32,
+    /// The fraction of each period the output is high, from `0.0` to `1.0`.
+    #[serde(default)]
+    pub duty_cycle: f32,
+}
+
 /// For SPI interfaces see [here](https://www.raspberrypi.com/documentation/computers/raspberry-pi.html#serial-peripheral-interface-spi)
 ///
 /// Standard mode
@@ -42,7 +69,7 @@ impl fmt::Display for InputPull {
 /// * SCLK - serial clock
 /// * CE   - chip enable (often called chip select)
 /// * MOMI - master out master in
-#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
 #[allow(non_camel_case_types)]
 #[allow(clippy::upper_case_acronyms)]
 pub enum PinFunction {
@@ -58,9 +85,9 @@ pub enum PinFunction {
     Output(Option<PinLevel>),
 
     /// General Purpose Clock functions (from https://pinout.xyz/pinout/gpclk)
-    GPCLK0,
-    GPCLK1,
-    GPCLK2,
+    GPCLK0(ClockConfig),
+    GPCLK1(ClockConfig),
+    GPCLK2(ClockConfig),
 
     /// I2C bus functions
     I2C1_SDA,
@@ -94,8 +121,8 @@ pub enum PinFunction {
     SPI1_CE2_N,
 
     /// PWM functions - two pins each use these
-    PWM0,
-    PWM1,
+    PWM0(PwmConfig),
+    PWM1(PwmConfig),
 
     /// UART functions
     /// UART0 - Transmit
@@ -119,16 +146,113 @@ pub enum PinFunction {
 
 impl fmt::Display for PinFunction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // Remove anything after the first '(' of debug output
-        let full = format!("{:?}", self);
-        write!(f, "{}", full.split_once('(').unwrap_or((&full, "")).0)
+        match self {
+            PinFunction::GPCLK0(clock) | PinFunction::GPCLK1(clock) | PinFunction::GPCLK2(clock) => {
+                // Remove anything after the first '(' of debug output, same as the fallback
+                // below, to get the bare variant name ("GPCLK0" etc).
+                let full = format!("{:?}", self);
+                let name = full.split_once('(').unwrap_or((&full, "")).0;
+                write!(f, "{name} ({} Hz)", clock.frequency_hz)
+            }
+            PinFunction::PWM0(pwm) | PinFunction::PWM1(pwm) => {
+                let full = format!("{:?}", self);
+                let name = full.split_once('(').unwrap_or((&full, "")).0;
+                write!(f, "{name} ({} Hz, {:.0}% duty)", pwm.frequency_hz, pwm.duty_cycle * 100.0)
+            }
+            _ => {
+                // Remove anything after the first '(' of debug output
+                let full = format!("{:?}", self);
+                write!(f, "{}", full.split_once('(').unwrap_or((&full, "")).0)
+            }
+        }
+    }
+}
+
+/// Deserializes [`PinFunction`], accepting the pre-existing on-disk representation of
+/// `GPCLKn`/`PWMn` as a bare variant name (with no payload) and defaulting their
+/// [`ClockConfig`]/[`PwmConfig`] in that case, so `.piggui` files saved before those variants
+/// carried configuration still load.
+impl<'de> Deserialize<'de> for PinFunction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        if let serde_json::Value::String(tag) = &value {
+            let defaulted = match tag.as_str() {
+                "GPCLK0" => Some(PinFunction::GPCLK0(ClockConfig::default())),
+                "GPCLK1" => Some(PinFunction::GPCLK1(ClockConfig::default())),
+                "GPCLK2" => Some(PinFunction::GPCLK2(ClockConfig::default())),
+                "PWM0" => Some(PinFunction::PWM0(PwmConfig::default())),
+                "PWM1" => Some(PinFunction::PWM1(PwmConfig::default())),
+                _ => None,
+            };
+            if let Some(function) = defaulted {
+                return Ok(function);
+            }
+        }
+
+        PinFunctionRepr::deserialize(value).map_err(serde::de::Error::custom)
     }
 }
 
+/// Mirror of [`PinFunction`] whose derived `Deserialize` (via `#[serde(remote = "PinFunction")]`)
+/// handles every variant in its current, post-payload shape. Kept in sync with [`PinFunction`]
+/// by hand - [`PinFunction`]'s own `Deserialize` impl only special-cases the handful of
+/// variants with a backward-compatible on-disk representation.
+#[derive(Deserialize)]
+#[serde(remote = "PinFunction")]
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+enum PinFunctionRepr {
+    None,
+    Power3V3,
+    Power5V,
+    Ground,
+    Input(Option<InputPull>),
+    Output(Option<PinLevel>),
+    GPCLK0(ClockConfig),
+    GPCLK1(ClockConfig),
+    GPCLK2(ClockConfig),
+    I2C1_SDA,
+    I2C1_SCL,
+    I2C3_SDA,
+    I2C3_SCL,
+    I2C4_SDA,
+    I2C4_SCL,
+    I2C5_SDA,
+    I2C5_SCL,
+    I2C6_SDA,
+    I2C6_SCL,
+    SPI0_MOSI,
+    SPI0_MOMI,
+    SPI0_MISO,
+    SPI0_SCLK,
+    SPI0_CE0_N,
+    SPI0_CE1_N,
+    SPI1_MOSI,
+    SPI1_MOMI,
+    SPI1_MISO,
+    SPI1_SCLK,
+    SPI1_CE0_N,
+    SPI1_CE1_N,
+    SPI1_CE2_N,
+    PWM0(PwmConfig),
+    PWM1(PwmConfig),
+    UART0_TXD,
+    UART0_RXD,
+    PCM_FS,
+    PCM_DIN,
+    PCM_DOUT,
+    PCM_CLK,
+    I2C_EEPROM_ID_SD,
+    I2C_EEPROM_ID_SC,
+}
+
 // [board_pin_number] refer to the pins by the number of the pin printed on the board
 // [bcm_pin_number] refer to the pins by the "Broadcom SOC channel" number,
 // these are the numbers after "GPIO"
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct PinDescription {
     pub board_pin_number: BoardPinNumber,
     pub bcm_pin_number: Option<BCMPinNumber>,
@@ -145,6 +269,62 @@ impl fmt::Display for PinDescription {
     }
 }
 
+/// The 40-pin GPIO header layout shared by all modern (post Model B+) Raspberry Pi boards.
+///
+/// Indexed by `board_pin_number - 1`. Pins with no `bcm_pin_number` are power/ground pins
+/// whose only option is their fixed [`PinFunction::Power3V3`], [`PinFunction::Power5V`] or
+/// [`PinFunction::Ground`].
+pub const PIN_DESCRIPTIONS: [PinDescription; 40] = [
+    PinDescription { board_pin_number: 1, bcm_pin_number: None, name: "3V3", options: &[PinFunction::Power3V3] },
+    PinDescription { board_pin_number: 2, bcm_pin_number: None, name: "5V", options: &[PinFunction::Power5V] },
+    PinDescription { board_pin_number: 3, bcm_pin_number: Some(2), name: "GPIO2", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::I2C1_SDA] },
+    PinDescription { board_pin_number: 4, bcm_pin_number: None, name: "5V", options: &[PinFunction::Power5V] },
+    PinDescription { board_pin_number: 5, bcm_pin_number: Some(3), name: "GPIO3", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::I2C1_SCL] },
+    PinDescription { board_pin_number: 6, bcm_pin_number: None, name: "Ground", options: &[PinFunction::Ground] },
+    PinDescription { board_pin_number: 7, bcm_pin_number: Some(4), name: "GPIO4", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::GPCLK0(ClockConfig { frequency_hz: 0 }), PinFunction::I2C3_SDA] },
+    PinDescription { board_pin_number: 8, bcm_pin_number: Some(14), name: "GPIO14", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::UART0_TXD] },
+    PinDescription { board_pin_number: 9, bcm_pin_number: None, name: "Ground", options: &[PinFunction::Ground] },
+    PinDescription { board_pin_number: 10, bcm_pin_number: Some(15), name: "GPIO15", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::UART0_RXD] },
+    PinDescription { board_pin_number: 11, bcm_pin_number: Some(17), name: "GPIO17", options: &[PinFunction::Input(None), PinFunction::Output(None)] },
+    PinDescription { board_pin_number: 12, bcm_pin_number: Some(18), name: "GPIO18", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::PCM_CLK, PinFunction::PWM0(PwmConfig { frequency_hz: 0, duty_cycle: 0.0 })] },
+    PinDescription { board_pin_number: 13, bcm_pin_number: Some(27), name: "GPIO27", options: &[PinFunction::Input(None), PinFunction::Output(None)] },
+    PinDescription { board_pin_number: 14, bcm_pin_number: None, name: "Ground", options: &[PinFunction::Ground] },
+    PinDescription { board_pin_number: 15, bcm_pin_number: Some(22), name: "GPIO22", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::I2C6_SDA] },
+    PinDescription { board_pin_number: 16, bcm_pin_number: Some(23), name: "GPIO23", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::I2C6_SCL] },
+    PinDescription { board_pin_number: 17, bcm_pin_number: None, name: "3V3", options: &[PinFunction::Power3V3] },
+    PinDescription { board_pin_number: 18, bcm_pin_number: Some(24), name: "GPIO24", options: &[PinFunction::Input(None), PinFunction::Output(None)] },
+    PinDescription { board_pin_number: 19, bcm_pin_number: Some(10), name: "GPIO10", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::SPI0_MOSI, PinFunction::SPI0_MOMI] },
+    PinDescription { board_pin_number: 20, bcm_pin_number: None, name: "Ground", options: &[PinFunction::Ground] },
+    PinDescription { board_pin_number: 21, bcm_pin_number: Some(9), name: "GPIO9", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::SPI0_MISO, PinFunction::I2C4_SCL] },
+    PinDescription { board_pin_number: 22, bcm_pin_number: Some(25), name: "GPIO25", options: &[PinFunction::Input(None), PinFunction::Output(None)] },
+    PinDescription { board_pin_number: 23, bcm_pin_number: Some(11), name: "GPIO11", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::SPI0_SCLK] },
+    PinDescription { board_pin_number: 24, bcm_pin_number: Some(8), name: "GPIO8", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::SPI0_CE0_N, PinFunction::I2C4_SDA] },
+    PinDescription { board_pin_number: 25, bcm_pin_number: None, name: "Ground", options: &[PinFunction::Ground] },
+    PinDescription { board_pin_number: 26, bcm_pin_number: Some(7), name: "GPIO7", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::SPI0_CE1_N, PinFunction::I2C3_SCL] },
+    PinDescription { board_pin_number: 27, bcm_pin_number: Some(0), name: "GPIO0", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::I2C_EEPROM_ID_SD] },
+    PinDescription { board_pin_number: 28, bcm_pin_number: Some(1), name: "GPIO1", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::I2C_EEPROM_ID_SC] },
+    PinDescription { board_pin_number: 29, bcm_pin_number: Some(5), name: "GPIO5", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::I2C3_SCL] },
+    PinDescription { board_pin_number: 30, bcm_pin_number: None, name: "Ground", options: &[PinFunction::Ground] },
+    PinDescription { board_pin_number: 31, bcm_pin_number: Some(6), name: "GPIO6", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::I2C3_SDA] },
+    PinDescription { board_pin_number: 32, bcm_pin_number: Some(12), name: "GPIO12", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::PWM0(PwmConfig { frequency_hz: 0, duty_cycle: 0.0 }), PinFunction::I2C5_SDA] },
+    PinDescription { board_pin_number: 33, bcm_pin_number: Some(13), name: "GPIO13", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::PWM1(PwmConfig { frequency_hz: 0, duty_cycle: 0.0 }), PinFunction::I2C5_SCL] },
+    PinDescription { board_pin_number: 34, bcm_pin_number: None, name: "Ground", options: &[PinFunction::Ground] },
+    PinDescription { board_pin_number: 35, bcm_pin_number: Some(19), name: "GPIO19", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::PCM_FS, PinFunction::PWM1(PwmConfig { frequency_hz: 0, duty_cycle: 0.0 })] },
+    PinDescription { board_pin_number: 36, bcm_pin_number: Some(16), name: "GPIO16", options: &[PinFunction::Input(None), PinFunction::Output(None)] },
+    PinDescription { board_pin_number: 37, bcm_pin_number: Some(26), name: "GPIO26", options: &[PinFunction::Input(None), PinFunction::Output(None)] },
+    PinDescription { board_pin_number: 38, bcm_pin_number: Some(20), name: "GPIO20", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::PCM_DIN] },
+    PinDescription { board_pin_number: 39, bcm_pin_number: None, name: "Ground", options: &[PinFunction::Ground] },
+    PinDescription { board_pin_number: 40, bcm_pin_number: Some(21), name: "GPIO21", options: &[PinFunction::Input(None), PinFunction::Output(None), PinFunction::PCM_DOUT] },
+];
+
+/// Look up the [`PinDescription`] for a given BCM GPIO number, if one exists on the
+/// standard 40-pin header.
+pub fn pin_description(bcm_pin_number: BCMPinNumber) -> Option<&'static PinDescription> {
+    PIN_DESCRIPTIONS
+        .iter()
+        .find(|pin| pin.bcm_pin_number == Some(bcm_pin_number))
+}
+
 /// A vector of tuples of (bcm_pin_number, PinFunction)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct GPIOConfig {
@@ -191,14 +371,22 @@ pub type PinLevel = bool;
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "gui")]
     use std::fs;
+    #[cfg(feature = "gui")]
     use std::fs::File;
+    #[cfg(feature = "gui")]
     use std::io::Write;
+    #[cfg(feature = "gui")]
     use std::path::PathBuf;
 
+    #[cfg(feature = "gui")]
     use tempfile::tempdir;
 
-    use crate::gpio::{GPIOConfig, PinFunction};
+    use crate::gpio::GPIOConfig;
+    #[cfg(feature = "gui")]
+    use crate::gpio::{ClockConfig, PinFunction, PwmConfig};
+    #[cfg(feature = "gui")]
     use crate::gpio::InputPull::PullUp;
 
     #[test]
@@ -208,6 +396,7 @@ mod test {
     }
 
     #[test]
+    #[cfg(feature = "gui")]
     fn save_one_pin_config_input_no_pullup() {
         let config = GPIOConfig {
             configured_pins: vec![(1, PinFunction::Input(None))],
@@ -224,6 +413,7 @@ mod test {
     }
 
     #[test]
+    #[cfg(feature = "gui")]
     fn load_one_pin_config_input_no_pull() {
         let pin_config = r#"{"configured_pins":[[1,{"Input":null}]]}"#;
         let output_dir = tempdir().expect("Could not create a tempdir").into_path();
@@ -238,6 +428,7 @@ mod test {
     }
 
     #[test]
+    #[cfg(feature = "gui")]
     fn load_test_file() {
         let root = std::env::var("CARGO_MANIFEST_DIR").expect("Could not get manifest dir");
         let mut path = PathBuf::from(root);
@@ -258,6 +449,7 @@ mod test {
     }
 
     #[test]
+    #[cfg(feature = "gui")]
     fn save_one_pin_config_output_with_level() {
         let config = GPIOConfig {
             configured_pins: vec![(7, PinFunction::Output(Some(true)))], // GPIO7 output set to 1
@@ -272,4 +464,62 @@ mod test {
         let contents = fs::read_to_string(test_file).expect("Could not read test file");
         assert_eq!(contents, pin_config);
     }
+
+    #[test]
+    #[cfg(feature = "gui")]
+    fn save_and_load_one_pin_config_gpclk() {
+        let config = GPIOConfig {
+            configured_pins: vec![(4, PinFunction::GPCLK0(ClockConfig { frequency_hz: 1_000_000 }))],
+        };
+
+        let output_dir = tempdir().expect("Could not create a tempdir").into_path();
+        let test_file = output_dir.join("test.piggui");
+
+        config.save(test_file.to_str().unwrap()).unwrap();
+        let loaded = GPIOConfig::load(test_file.to_str().unwrap()).unwrap();
+
+        assert_eq!(loaded.configured_pins, config.configured_pins);
+    }
+
+    #[test]
+    #[cfg(feature = "gui")]
+    fn save_and_load_one_pin_config_pwm() {
+        let config = GPIOConfig {
+            configured_pins: vec![(
+                18,
+                PinFunction::PWM0(PwmConfig {
+                    frequency_hz: 50,
+                    duty_cycle: 0.25,
+                }),
+            )],
+        };
+
+        let output_dir = tempdir().expect("Could not create a tempdir").into_path();
+        let test_file = output_dir.join("test.piggui");
+
+        config.save(test_file.to_str().unwrap()).unwrap();
+        let loaded = GPIOConfig::load(test_file.to_str().unwrap()).unwrap();
+
+        assert_eq!(loaded.configured_pins, config.configured_pins);
+    }
+
+    #[test]
+    #[cfg(feature = "gui")]
+    fn load_pre_payload_gpclk_and_pwm_defaults_config() {
+        // `.piggui` files saved before GPCLKn/PWMn carried configuration stored them as bare
+        // variant names with no payload.
+        let pin_config = r#"{"configured_pins":[[4,"GPCLK0"],[18,"PWM0"]]}"#;
+        let output_dir = tempdir().expect("Could not create a tempdir").into_path();
+        let test_file = output_dir.join("test.piggui");
+        let mut file = File::create(&test_file).expect("Could not create test file");
+        file.write_all(pin_config.as_bytes())
+            .expect("Could not write to test file");
+
+        let config = GPIOConfig::load(test_file.to_str().unwrap()).unwrap();
+        assert_eq!(
+            config.configured_pins[0].1,
+            PinFunction::GPCLK0(ClockConfig::default())
+        );
+        assert_eq!(config.configured_pins[1].1, PinFunction::PWM0(PwmConfig::default()));
+    }
 }
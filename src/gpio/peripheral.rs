@@ -0,0 +1,367 @@
+//! Bus-level configuration for the SPI/I2C/UART signals enumerated individually in
+//! [`PinFunction`].
+//!
+//! [`GPIOConfig`] only knows about individual `(BCMPinNumber, PinFunction)` pairs, so nothing
+//! stops a user from wiring together an incomplete or incapable set of pins for a bus (e.g.
+//! an SPI config missing `SCLK`, or I2C `SDA` placed on a pin that can't do I2C). The types in
+//! this module are built from a specific, complete pin assignment for a bus - modelled as an
+//! enum of the valid pin-set shapes, following the pin-enum approach used by
+//! `stm32f1xx-hal` - and validate each assigned pin against its [`PinDescription::options`]
+//! before they can be [`lower`](SpiConfig::lower)ed into `GPIOConfig` entries.
+
+use std::fmt;
+
+use crate::gpio::hardware::supports_function;
+use crate::gpio::{pin_description, BCMPinNumber, GPIOConfig, PinFunction};
+
+/// A pin was assigned a bus signal its [`PinDescription::options`] does not list.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PeripheralError {
+    pub bus: &'static str,
+    pub signal: &'static str,
+    pub bcm_pin_number: BCMPinNumber,
+    pub function: PinFunction,
+}
+
+impl fmt::Display for PeripheralError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: BCM pin {} does not support {}",
+            self.bus, self.signal, self.bcm_pin_number, self.function
+        )
+    }
+}
+
+impl std::error::Error for PeripheralError {}
+
+fn require(
+    bus: &'static str,
+    signal: &'static str,
+    bcm_pin_number: BCMPinNumber,
+    function: PinFunction,
+) -> Result<(BCMPinNumber, PinFunction), PeripheralError> {
+    let supported = pin_description(bcm_pin_number)
+        .map(|description| supports_function(description, &function))
+        .unwrap_or(false);
+
+    if supported {
+        Ok((bcm_pin_number, function))
+    } else {
+        Err(PeripheralError {
+            bus,
+            signal,
+            bcm_pin_number,
+            function,
+        })
+    }
+}
+
+/// The two mutually-exclusive wirings SPI interface 0 can be assembled as: the standard
+/// four-wire protocol, or the bidirectional single-data-wire mode where `MOSI` is repurposed
+/// as `MOMI`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Spi0Pins {
+    Standard {
+        mosi: BCMPinNumber,
+        miso: BCMPinNumber,
+        sclk: BCMPinNumber,
+        ce: BCMPinNumber,
+    },
+    Bidirectional {
+        momi: BCMPinNumber,
+        sclk: BCMPinNumber,
+        ce: BCMPinNumber,
+    },
+}
+
+/// A validated assignment of BCM pins to SPI interface 0's signals.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SpiConfig {
+    pins: Spi0Pins,
+}
+
+impl SpiConfig {
+    /// Validate `pins` against each pin's [`PinDescription::options`], returning which
+    /// pin/signal is wrong if the assignment is not possible on this board.
+    pub fn new(pins: Spi0Pins) -> Result<Self, PeripheralError> {
+        match pins {
+            Spi0Pins::Standard { mosi, miso, sclk, ce } => {
+                require("SPI0", "MOSI", mosi, PinFunction::SPI0_MOSI)?;
+                require("SPI0", "MISO", miso, PinFunction::SPI0_MISO)?;
+                require("SPI0", "SCLK", sclk, PinFunction::SPI0_SCLK)?;
+                require("SPI0", "CE0", ce, PinFunction::SPI0_CE0_N)?;
+            }
+            Spi0Pins::Bidirectional { momi, sclk, ce } => {
+                require("SPI0", "MOMI", momi, PinFunction::SPI0_MOMI)?;
+                require("SPI0", "SCLK", sclk, PinFunction::SPI0_SCLK)?;
+                require("SPI0", "CE0", ce, PinFunction::SPI0_CE0_N)?;
+            }
+        }
+        Ok(Self { pins })
+    }
+
+    /// The `(BCMPinNumber, PinFunction)` entries this SPI configuration assigns, ready to be
+    /// appended to [`GPIOConfig::configured_pins`].
+    pub fn lower(&self) -> Vec<(BCMPinNumber, PinFunction)> {
+        match self.pins {
+            Spi0Pins::Standard { mosi, miso, sclk, ce } => vec![
+                (mosi, PinFunction::SPI0_MOSI),
+                (miso, PinFunction::SPI0_MISO),
+                (sclk, PinFunction::SPI0_SCLK),
+                (ce, PinFunction::SPI0_CE0_N),
+            ],
+            Spi0Pins::Bidirectional { momi, sclk, ce } => vec![
+                (momi, PinFunction::SPI0_MOMI),
+                (sclk, PinFunction::SPI0_SCLK),
+                (ce, PinFunction::SPI0_CE0_N),
+            ],
+        }
+    }
+
+    /// Append this SPI configuration's pins to `config`.
+    pub fn apply(&self, config: &mut GPIOConfig) {
+        config.configured_pins.extend(self.lower());
+    }
+}
+
+#[cfg(test)]
+mod spi_test {
+    use super::*;
+
+    #[test]
+    fn standard_pins_lower_to_four_signals() {
+        let spi = SpiConfig::new(Spi0Pins::Standard {
+            mosi: 10,
+            miso: 9,
+            sclk: 11,
+            ce: 8,
+        })
+        .unwrap();
+
+        assert_eq!(
+            spi.lower(),
+            vec![
+                (10, PinFunction::SPI0_MOSI),
+                (9, PinFunction::SPI0_MISO),
+                (11, PinFunction::SPI0_SCLK),
+                (8, PinFunction::SPI0_CE0_N),
+            ]
+        );
+    }
+
+    #[test]
+    fn pin_without_the_signal_is_rejected() {
+        let error = SpiConfig::new(Spi0Pins::Standard {
+            mosi: 17, // GPIO17 has no SPI0 option
+            miso: 9,
+            sclk: 11,
+            ce: 8,
+        })
+        .unwrap_err();
+
+        assert_eq!(
+            error,
+            PeripheralError {
+                bus: "SPI0",
+                signal: "MOSI",
+                bcm_pin_number: 17,
+                function: PinFunction::SPI0_MOSI,
+            }
+        );
+    }
+}
+
+/// The I2C buses routable to the header's alternate pin pairs.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum I2cBus {
+    I2C1,
+    I2C3,
+    I2C4,
+    I2C5,
+    I2C6,
+}
+
+impl I2cBus {
+    fn name(self) -> &'static str {
+        match self {
+            I2cBus::I2C1 => "I2C1",
+            I2cBus::I2C3 => "I2C3",
+            I2cBus::I2C4 => "I2C4",
+            I2cBus::I2C5 => "I2C5",
+            I2cBus::I2C6 => "I2C6",
+        }
+    }
+
+    fn sda(self) -> PinFunction {
+        match self {
+            I2cBus::I2C1 => PinFunction::I2C1_SDA,
+            I2cBus::I2C3 => PinFunction::I2C3_SDA,
+            I2cBus::I2C4 => PinFunction::I2C4_SDA,
+            I2cBus::I2C5 => PinFunction::I2C5_SDA,
+            I2cBus::I2C6 => PinFunction::I2C6_SDA,
+        }
+    }
+
+    fn scl(self) -> PinFunction {
+        match self {
+            I2cBus::I2C1 => PinFunction::I2C1_SCL,
+            I2cBus::I2C3 => PinFunction::I2C3_SCL,
+            I2cBus::I2C4 => PinFunction::I2C4_SCL,
+            I2cBus::I2C5 => PinFunction::I2C5_SCL,
+            I2cBus::I2C6 => PinFunction::I2C6_SCL,
+        }
+    }
+}
+
+/// The specific, non-interchangeable `SDA`/`SCL` pin pairs the header's alternate-function
+/// routing allows an I2C bus to use. I2C3 has two real physical pairs - GPIO4/GPIO5 and
+/// GPIO6/GPIO7 - and mixing `SDA` from one pair with `SCL` from the other is not a valid
+/// routing even though each pin individually supports an I2C3 signal. Like [`Spi0Pins`], each
+/// legal pairing gets its own variant instead of validating `sda` and `scl` as two
+/// independently-checked pins.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum I2cPins {
+    I2c1,
+    I2c3,
+    I2c3Alternate,
+    I2c4,
+    I2c5,
+    I2c6,
+}
+
+impl I2cPins {
+    fn bus(self) -> I2cBus {
+        match self {
+            I2cPins::I2c1 => I2cBus::I2C1,
+            I2cPins::I2c3 | I2cPins::I2c3Alternate => I2cBus::I2C3,
+            I2cPins::I2c4 => I2cBus::I2C4,
+            I2cPins::I2c5 => I2cBus::I2C5,
+            I2cPins::I2c6 => I2cBus::I2C6,
+        }
+    }
+
+    fn sda_pin(self) -> BCMPinNumber {
+        match self {
+            I2cPins::I2c1 => 2,
+            I2cPins::I2c3 => 4,
+            I2cPins::I2c3Alternate => 6,
+            I2cPins::I2c4 => 8,
+            I2cPins::I2c5 => 12,
+            I2cPins::I2c6 => 22,
+        }
+    }
+
+    fn scl_pin(self) -> BCMPinNumber {
+        match self {
+            I2cPins::I2c1 => 3,
+            I2cPins::I2c3 => 5,
+            I2cPins::I2c3Alternate => 7,
+            I2cPins::I2c4 => 9,
+            I2cPins::I2c5 => 13,
+            I2cPins::I2c6 => 23,
+        }
+    }
+}
+
+/// A validated assignment of BCM pins to one I2C bus's `SDA`/`SCL` signals.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct I2cConfig {
+    pins: I2cPins,
+}
+
+impl I2cConfig {
+    /// Validate `pins` against each pin's [`PinDescription::options`], returning which
+    /// pin/signal is wrong if the assignment is not possible on this board.
+    pub fn new(pins: I2cPins) -> Result<Self, PeripheralError> {
+        let bus = pins.bus();
+        require(bus.name(), "SDA", pins.sda_pin(), bus.sda())?;
+        require(bus.name(), "SCL", pins.scl_pin(), bus.scl())?;
+        Ok(Self { pins })
+    }
+
+    pub fn lower(&self) -> Vec<(BCMPinNumber, PinFunction)> {
+        let bus = self.pins.bus();
+        vec![(self.pins.sda_pin(), bus.sda()), (self.pins.scl_pin(), bus.scl())]
+    }
+
+    pub fn apply(&self, config: &mut GPIOConfig) {
+        config.configured_pins.extend(self.lower());
+    }
+}
+
+#[cfg(test)]
+mod i2c_test {
+    use super::*;
+
+    #[test]
+    fn alternate_bus_lowers_to_its_own_signals() {
+        let i2c = I2cConfig::new(I2cPins::I2c3Alternate).unwrap();
+        assert_eq!(
+            i2c.lower(),
+            vec![(6, PinFunction::I2C3_SDA), (7, PinFunction::I2C3_SCL)]
+        );
+    }
+
+    #[test]
+    fn primary_and_alternate_i2c3_pairs_cannot_be_mixed() {
+        // Each variant is its own self-contained pin pair - there is no way to ask for
+        // GPIO6's SDA alongside GPIO5's SCL, which is the invalid routing this type exists
+        // to rule out.
+        let primary = I2cConfig::new(I2cPins::I2c3).unwrap();
+        let alternate = I2cConfig::new(I2cPins::I2c3Alternate).unwrap();
+        assert_eq!(primary.lower(), vec![(4, PinFunction::I2C3_SDA), (5, PinFunction::I2C3_SCL)]);
+        assert_eq!(alternate.lower(), vec![(6, PinFunction::I2C3_SDA), (7, PinFunction::I2C3_SCL)]);
+    }
+}
+
+/// A validated assignment of BCM pins to UART0's `TXD`/`RXD` signals.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct UartConfig {
+    txd: BCMPinNumber,
+    rxd: BCMPinNumber,
+}
+
+impl UartConfig {
+    pub fn new(txd: BCMPinNumber, rxd: BCMPinNumber) -> Result<Self, PeripheralError> {
+        require("UART0", "TXD", txd, PinFunction::UART0_TXD)?;
+        require("UART0", "RXD", rxd, PinFunction::UART0_RXD)?;
+        Ok(Self { txd, rxd })
+    }
+
+    pub fn lower(&self) -> Vec<(BCMPinNumber, PinFunction)> {
+        vec![(self.txd, PinFunction::UART0_TXD), (self.rxd, PinFunction::UART0_RXD)]
+    }
+
+    pub fn apply(&self, config: &mut GPIOConfig) {
+        config.configured_pins.extend(self.lower());
+    }
+}
+
+#[cfg(test)]
+mod uart_test {
+    use super::*;
+
+    #[test]
+    fn txd_rxd_lower_to_uart0_signals() {
+        let uart = UartConfig::new(14, 15).unwrap();
+        assert_eq!(
+            uart.lower(),
+            vec![(14, PinFunction::UART0_TXD), (15, PinFunction::UART0_RXD)]
+        );
+    }
+
+    #[test]
+    fn swapped_pins_are_rejected() {
+        let error = UartConfig::new(15, 14).unwrap_err(); // TXD/RXD swapped
+        assert_eq!(
+            error,
+            PeripheralError {
+                bus: "UART0",
+                signal: "TXD",
+                bcm_pin_number: 15,
+                function: PinFunction::UART0_TXD,
+            }
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//! Resolves a set of peripheral requests onto the board's alternate pin routings, modeled on
+//! the `Rmp`/`RInto` remap pattern from `stm32f1xx-hal`: callers describe *which* bus they want
+//! and *which* of its valid [`PinDescription::options`]-backed pin sets to route it to, and
+//! [`remap`] validates and lowers every request in one pass, catching two peripherals that
+//! would otherwise claim the same BCM pin.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::gpio::peripheral::{I2cConfig, I2cPins, PeripheralError, Spi0Pins, SpiConfig, UartConfig};
+use crate::gpio::{BCMPinNumber, PinFunction};
+
+/// One peripheral's desired routing, to be validated and lowered by [`remap`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PinSet {
+    Spi0(Spi0Pins),
+    I2c(I2cPins),
+    Uart0 { txd: BCMPinNumber, rxd: BCMPinNumber },
+}
+
+/// Why a requested set of routings could not be resolved onto the board.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RemapError {
+    /// One of the requested pin sets is not legal for this board - see [`PeripheralError`].
+    Invalid(PeripheralError),
+    /// Two requested peripherals both claim `bcm_pin_number`, once for `first` and again for
+    /// `second`.
+    Conflict {
+        bcm_pin_number: BCMPinNumber,
+        first: PinFunction,
+        second: PinFunction,
+    },
+}
+
+impl fmt::Display for RemapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemapError::Invalid(error) => write!(f, "{error}"),
+            RemapError::Conflict { bcm_pin_number, first, second } => write!(
+                f,
+                "BCM pin {bcm_pin_number} was requested as both {first} and {second}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RemapError {}
+
+impl From<PeripheralError> for RemapError {
+    fn from(error: PeripheralError) -> Self {
+        RemapError::Invalid(error)
+    }
+}
+
+/// Validate and lower every [`PinSet`] in `requests` against the board's
+/// [`crate::gpio::PinDescription::options`], returning the combined `(BCMPinNumber,
+/// PinFunction)` assignments or the first [`RemapError`] found - an illegal routing, or two
+/// requests claiming the same BCM pin.
+pub fn remap(requests: &[PinSet]) -> Result<Vec<(BCMPinNumber, PinFunction)>, RemapError> {
+    let mut claimed: HashMap<BCMPinNumber, PinFunction> = HashMap::new();
+    let mut assignments = Vec::new();
+
+    for request in requests {
+        let lowered = match *request {
+            PinSet::Spi0(pins) => SpiConfig::new(pins)?.lower(),
+            PinSet::I2c(pins) => I2cConfig::new(pins)?.lower(),
+            PinSet::Uart0 { txd, rxd } => UartConfig::new(txd, rxd)?.lower(),
+        };
+
+        for (bcm_pin_number, function) in lowered {
+            if let Some(&first) = claimed.get(&bcm_pin_number) {
+                return Err(RemapError::Conflict {
+                    bcm_pin_number,
+                    first,
+                    second: function,
+                });
+            }
+            claimed.insert(bcm_pin_number, function);
+            assignments.push((bcm_pin_number, function));
+        }
+    }
+
+    Ok(assignments)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn routes_i2c_to_an_alternate_pin_pair() {
+        let assignments = remap(&[PinSet::I2c(I2cPins::I2c3Alternate)]).unwrap();
+        assert_eq!(
+            assignments,
+            vec![(6, PinFunction::I2C3_SDA), (7, PinFunction::I2C3_SCL)]
+        );
+    }
+
+    #[test]
+    fn two_peripherals_claiming_the_same_pin_conflict() {
+        let error = remap(&[
+            PinSet::Spi0(Spi0Pins::Standard { mosi: 10, miso: 9, sclk: 11, ce: 8 }),
+            // GPIO8/9 also do I2C4, but are already claimed above as SPI0 CE0/MISO.
+            PinSet::I2c(I2cPins::I2c4),
+        ])
+        .unwrap_err();
+
+        assert_eq!(
+            error,
+            RemapError::Conflict {
+                bcm_pin_number: 8,
+                first: PinFunction::SPI0_CE0_N,
+                second: PinFunction::I2C4_SDA,
+            }
+        );
+    }
+
+    #[test]
+    fn primary_and_alternate_i2c3_pairs_can_both_be_routed() {
+        // GPIO4/GPIO5 (the primary pair) and GPIO6/GPIO7 (the alternate pair) are distinct
+        // pins, so requesting both at once is legal routing, not a conflict.
+        let assignments = remap(&[PinSet::I2c(I2cPins::I2c3), PinSet::I2c(I2cPins::I2c3Alternate)]).unwrap();
+        assert_eq!(
+            assignments,
+            vec![
+                (4, PinFunction::I2C3_SDA),
+                (5, PinFunction::I2C3_SCL),
+                (6, PinFunction::I2C3_SDA),
+                (7, PinFunction::I2C3_SCL),
+            ]
+        );
+    }
+
+    #[test]
+    fn illegal_routing_is_reported_as_invalid() {
+        let error = remap(&[PinSet::Uart0 { txd: 15, rxd: 14 }]).unwrap_err(); // TXD/RXD swapped
+        assert!(matches!(error, RemapError::Invalid(_)));
+    }
+}